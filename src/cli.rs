@@ -0,0 +1,17 @@
+use structopt::clap::arg_enum;
+
+arg_enum! {
+    /// How to treat an already existing `README.md` when generating a new one.
+    #[derive(Debug, PartialEq, Clone)]
+    pub enum GenerateReadmeMode {
+        /// Only generate the readme if none exists yet.
+        IfMissing,
+        /// Append the generated content to whatever is already there.
+        Append,
+        /// Overwrite the whole file with the generated content.
+        Overwrite,
+        /// Keep hand-written content and only replace the generated block
+        /// between the `cargo-unleash` sync markers.
+        Sync,
+    }
+}
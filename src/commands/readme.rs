@@ -1,29 +1,29 @@
 use crate::cli::GenerateReadmeMode;
 use crate::commands;
 use cargo::core::{Manifest, Package, Workspace};
-use lazy_static::lazy_static;
-use regex::{Captures, Regex};
+use pulldown_cmark::{CodeBlockKind, Event, LinkType, Parser, Tag};
 use sha1::Sha1;
 use std::{
     error::Error,
     fmt::Display,
     fs::{self, File},
     path::{Path, PathBuf},
+    process::Command,
 };
+use tempfile::tempdir;
 use toml_edit::Value;
 
 static DEFAULT_DOC_URI: &str = "https://docs.rs/";
-
-lazy_static! {
-    // See http://blog.michaelperrin.fr/2019/02/04/advanced-regular-expressions/
-    static ref RELATIVE_LINKS_REGEX: Regex = 
-        Regex::new(r#"\[(?P<text>.+)\]\((?P<url>[^ ]+)(?: "(?P<title>.+)")?\)"#).unwrap();
-}
+static SYNC_START_MARKER: &str = "<!-- cargo-unleash:start -->";
+static SYNC_END_MARKER: &str = "<!-- cargo-unleash:end -->";
 
 #[derive(Debug)]
 pub enum CheckReadmeResult {
     Skipped,
     Missing,
+    /// A doc source exists, but not where `cargo-unleash` would generate
+    /// one (e.g. only found in a parent directory, up to the workspace root).
+    WrongLocation,
     UpdateNeeded,
     UpToDate,
 }
@@ -36,6 +36,7 @@ impl Display for CheckReadmeResult {
             match self {
                 Self::Skipped => "Skipped",
                 Self::Missing => "Missing",
+                Self::WrongLocation => "Wrong location",
                 Self::UpdateNeeded => "Update needed",
                 Self::UpToDate => "Up-to-date",
             }
@@ -43,6 +44,119 @@ impl Display for CheckReadmeResult {
     }
 }
 
+/// The markup language a resolved doc source is written in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DocMarkup {
+    Markdown,
+    ReStructuredText,
+    /// Anything else (plain text, AsciiDoc, ...) — the docs.rs link-fixing
+    /// rules only apply to Markdown, so downstream generation skips them.
+    Other,
+}
+
+impl DocMarkup {
+    fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("md") | Some("markdown") => DocMarkup::Markdown,
+            Some("rst") => DocMarkup::ReStructuredText,
+            _ => DocMarkup::Other,
+        }
+    }
+}
+
+/// Where a resolved doc source was actually found, so a README that's
+/// simply missing can be told apart from one that exists but isn't where
+/// `cargo-unleash` expects it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DocSourceLocation {
+    /// The manifest's `readme` field (or its default) pointed straight at it.
+    Declared,
+    /// Found under a conventional name in the package's own directory.
+    PackageDir,
+    /// Only found by walking up to the workspace root.
+    WorkspaceRoot,
+    /// No README file anywhere; falling back to the crate's doc comment.
+    DocComment,
+}
+
+/// The result of the multi-stage doc source lookup: an explicit manifest
+/// hint, the package directory, an upward walk to the workspace root (the
+/// same walk `find_readme_template` does for `README.tpl`), and finally the
+/// lib/main doc comment as a last resort.
+#[derive(Debug)]
+struct DocSource {
+    path: PathBuf,
+    markup: DocMarkup,
+    location: DocSourceLocation,
+}
+
+static CONVENTIONAL_DOC_FILES: &[&str] =
+    &["README.md", "README.markdown", "README.rst", "README.txt"];
+
+fn find_conventional_doc_file(dir: &Path) -> Option<PathBuf> {
+    CONVENTIONAL_DOC_FILES
+        .iter()
+        .map(|name| dir.join(name))
+        .find(|p| p.exists())
+}
+
+/// Resolve the package's documentation source, analogous to the two-stage
+/// path lookup crates.rs tooling uses for READMEs, extended with a final
+/// fallback to the extracted doc comment.
+///
+/// Returns `None` when the package has explicitly opted out via
+/// `readme = false`.
+fn resolve_doc_source<'a>(
+    ws: &Workspace<'a>,
+    pkg_path: &Path,
+    manifest_path: &Path,
+    entrypoint: &Path,
+) -> Result<Option<DocSource>, String> {
+    // Stage 1: an explicit `readme`/`readme.workspace` hint in the manifest.
+    let declared = match resolve_readme_path(ws, pkg_path, manifest_path)? {
+        Some(path) => path,
+        None => return Ok(None),
+    };
+    if declared.exists() {
+        return Ok(Some(DocSource {
+            markup: DocMarkup::from_path(&declared),
+            path: declared,
+            location: DocSourceLocation::Declared,
+        }));
+    }
+
+    // Stage 2: search the package's own directory for a conventional name.
+    if let Some(found) = find_conventional_doc_file(pkg_path) {
+        return Ok(Some(DocSource {
+            markup: DocMarkup::from_path(&found),
+            path: found,
+            location: DocSourceLocation::PackageDir,
+        }));
+    }
+
+    // Stage 3: walk up to the workspace root, same as `find_readme_template`.
+    let root_path = ws.root();
+    let mut cur_path = pkg_path;
+    while cur_path > root_path {
+        cur_path = cur_path.parent().unwrap();
+        if let Some(found) = find_conventional_doc_file(cur_path) {
+            return Ok(Some(DocSource {
+                markup: DocMarkup::from_path(&found),
+                path: found,
+                location: DocSourceLocation::WorkspaceRoot,
+            }));
+        }
+    }
+
+    // Stage 4: no README file anywhere; fall back to the doc comment that
+    // `cargo-unleash` would generate one from.
+    Ok(Some(DocSource {
+        path: entrypoint.to_owned(),
+        markup: DocMarkup::Markdown,
+        location: DocSourceLocation::DocComment,
+    }))
+}
+
 pub fn check_pkg_readme<'a>(
     ws: &Workspace<'a>,
     pkg_path: &Path,
@@ -50,20 +164,46 @@ pub fn check_pkg_readme<'a>(
 ) -> Result<(), String> {
     let c = ws.config();
 
-    let mut pkg_source = find_entrypoint(pkg_path)?;
-    let readme_path = pkg_path.join("README.md");
+    let mut pkg_source = find_entrypoint(pkg_path, pkg_manifest)?;
+    let entrypoint = find_entrypoint_internal(pkg_path, pkg_manifest)?;
+    let manifest_path = pkg_path.join("Cargo.toml");
 
     c.shell()
         .status("Checking", format!("Readme for {}", &pkg_manifest.name()))
         .map_err(|e| format!("{:}", e))?;
 
-    let pkg_readme = fs::read_to_string(readme_path.clone());
+    let doc_source = match resolve_doc_source(ws, pkg_path, &manifest_path, &entrypoint)? {
+        Some(source) => source,
+        None => return Err(CheckReadmeResult::Skipped.to_string()),
+    };
+
+    match doc_source.location {
+        DocSourceLocation::DocComment => return Err(CheckReadmeResult::Missing.to_string()),
+        DocSourceLocation::WorkspaceRoot => {
+            return Err(CheckReadmeResult::WrongLocation.to_string())
+        }
+        DocSourceLocation::Declared | DocSourceLocation::PackageDir => {}
+    }
+
+    if doc_source.markup != DocMarkup::Markdown {
+        // The docs.rs rewrite rules, and the generated-content diff below,
+        // only make sense for Markdown; anything else is left as-is.
+        return Ok(());
+    }
+
+    let pkg_readme = fs::read_to_string(&doc_source.path);
     match pkg_readme {
         Ok(pkg_readme) => {
             // Try to find readme template
             let template_path = find_readme_template(&ws.root(), &pkg_path)?;
 
             let new_readme = generate_readme(&pkg_path, &mut pkg_source, template_path)?;
+            check_readme_examples_compile(
+                pkg_path,
+                &pkg_manifest.name().to_string(),
+                &pkg_manifest.edition().to_string(),
+                &new_readme,
+            )?;
             if Sha1::from(pkg_readme) == Sha1::from(new_readme) {
                 Ok(())
             } else {
@@ -100,12 +240,37 @@ pub fn gen_pkg_readme<'a>(
 
     let pkg_manifest = pkg.manifest();
     let pkg_path = pkg.manifest_path().parent().expect("Folder exists");
-    
+
     let pkg_name = pkg_manifest.name();
     let doc_uri = pkg_manifest.metadata().documentation.as_ref();
 
-    let mut pkg_source = find_entrypoint(pkg_path)?;
-    let readme_path = pkg_path.join("README.md");
+    let mut pkg_source = find_entrypoint(pkg_path, pkg_manifest)?;
+    let readme_path = match resolve_readme_path(ws, pkg_path, pkg.manifest_path())? {
+        Some(p) => p,
+        None => {
+            c.shell()
+                .status("Skipping", format!("{}: `readme = false`.", &pkg_name))
+                .map_err(|e| format!("{:}", e))?;
+            return Ok(());
+        }
+    };
+
+    if !readme_path.starts_with(pkg_path) {
+        // `readme.workspace = true` points at a file shared with (and owned
+        // by) the workspace root; generating here would clobber it and race
+        // every other member inheriting the same file.
+        c.shell()
+            .status(
+                "Skipping",
+                format!(
+                    "{}: readme is inherited from the workspace root ({}); generate it there directly.",
+                    &pkg_name,
+                    readme_path.display()
+                ),
+            )
+            .map_err(|e| format!("{:}", e))?;
+        return Ok(());
+    }
 
     let pkg_readme = fs::read_to_string(readme_path.clone());
     match (mode, pkg_readme) {
@@ -113,7 +278,7 @@ pub fn gen_pkg_readme<'a>(
             c.shell()
                 .status("Skipping", format!("{}: Readme already exists.", &pkg_name))
                 .map_err(|e| format!("{:}", e))?;
-            set_readme_field(pkg).map_err(|e| format!("{:}", e))?;
+            set_readme_field(pkg, &readme_path).map_err(|e| format!("{:}", e))?;
             Ok(())
         }
         (mode, existing_res) => {
@@ -131,13 +296,22 @@ pub fn gen_pkg_readme<'a>(
                     ),
                 )
                 .map_err(|e| format!("{:}", e))?;
-            let new_readme = &mut generate_readme(&pkg_path, &mut pkg_source, template_path)?;
+            let generated = generate_readme(&pkg_path, &mut pkg_source, template_path)?;
+            // Rewrite links in the freshly generated body only, before it's
+            // combined with any hand-written content below: `fix_doc_links`
+            // must never touch text Append/Sync preserve verbatim.
+            let new_readme = &mut if DocMarkup::from_path(&readme_path) == DocMarkup::Markdown {
+                fix_doc_links(&pkg_name, &generated, doc_uri.map(|x| x.as_str()))
+            } else {
+                generated
+            };
             if mode == &GenerateReadmeMode::Append && existing_res.is_ok() {
                 *new_readme = format!("{}\n{}", existing_res.unwrap(), new_readme);
+            } else if mode == &GenerateReadmeMode::Sync {
+                *new_readme = sync_readme(existing_res.ok().as_deref(), new_readme)?;
             }
-            let final_readme = &mut fix_doc_links(&pkg_name, &new_readme, doc_uri.map(|x| x.as_str()));
-            let res = fs::write(readme_path, final_readme.as_bytes()).map_err(|e| format!("{:}", e));
-            set_readme_field(pkg).map_err(|e| format!("{:}", e))?;
+            let res = fs::write(&readme_path, new_readme.as_bytes()).map_err(|e| format!("{:}", e));
+            set_readme_field(pkg, &readme_path).map_err(|e| format!("{:}", e))?;
             res
         }
     }
@@ -162,23 +336,362 @@ fn generate_readme<'a>(
     )
 }
 
-fn set_readme_field(pkg: Package) -> Result<(), Box<dyn Error>> {
+/// A fenced (or indented) Rust code block extracted from a README, along
+/// with the rustdoc-style attributes from its info string.
+#[derive(Debug)]
+struct ReadmeExample {
+    /// 1-based line the block starts on, for error reporting.
+    line: usize,
+    code: String,
+    no_run: bool,
+    should_panic: bool,
+    compile_fail: bool,
+    ignore: bool,
+}
+
+/// Decide whether a code block's info string marks it as a Rust example,
+/// the same way rustdoc does: no info string (or the bare `rust` tag) is
+/// Rust, as are `no_run`/`should_panic`/`ignore`/`compile_fail`; anything
+/// else (`sh`, `toml`, ...) is assumed to be a different language.
+fn rust_example_attrs(kind: &CodeBlockKind) -> Option<ReadmeExample> {
+    let info = match kind {
+        CodeBlockKind::Indented => "",
+        CodeBlockKind::Fenced(info) => info.as_ref(),
+    };
+
+    let mut example = ReadmeExample {
+        line: 0,
+        code: String::new(),
+        no_run: false,
+        should_panic: false,
+        compile_fail: false,
+        ignore: false,
+    };
+    let mut is_rust = info.trim().is_empty();
+    for token in info.split(',').map(str::trim).filter(|t| !t.is_empty()) {
+        match token {
+            "rust" => is_rust = true,
+            "no_run" => {
+                is_rust = true;
+                example.no_run = true;
+            }
+            "should_panic" => {
+                is_rust = true;
+                example.should_panic = true;
+            }
+            "ignore" => {
+                is_rust = true;
+                example.ignore = true;
+            }
+            "compile_fail" => {
+                is_rust = true;
+                example.compile_fail = true;
+            }
+            _ => return None,
+        }
+    }
+
+    is_rust.then(|| example)
+}
+
+/// Walk the README with a real markdown parser and collect every Rust code
+/// block, recording the line it starts on so failures can be reported back
+/// against the README.
+fn extract_readme_examples(readme: &str) -> Vec<ReadmeExample> {
+    let mut examples = Vec::new();
+    let mut current: Option<ReadmeExample> = None;
+
+    for (event, range) in Parser::new(readme).into_offset_iter() {
+        match event {
+            Event::Start(Tag::CodeBlock(kind)) => {
+                if let Some(mut example) = rust_example_attrs(&kind) {
+                    example.line = readme[..range.start].matches('\n').count() + 1;
+                    current = Some(example);
+                }
+            }
+            Event::Text(text) => {
+                if let Some(example) = current.as_mut() {
+                    example.code.push_str(&text);
+                }
+            }
+            Event::End(Tag::CodeBlock(_)) => {
+                if let Some(example) = current.take() {
+                    examples.push(example);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    examples
+}
+
+/// rustdoc wraps bare statements in a `fn main`; do the same so snippets
+/// that don't declare their own `main` still compile.
+fn wrap_example(code: &str) -> String {
+    if code.contains("fn main") {
+        code.to_owned()
+    } else {
+        format!("fn main() {{\n{}\n}}\n", code)
+    }
+}
+
+/// Compile (and, unless `no_run`/`compile_fail`, run) a single README
+/// example against the package under test, the same way `cargo test --doc`
+/// exercises rustdoc examples. The example is written into a throwaway
+/// scratch crate (a temp dir with its own `Cargo.toml` path-depending on
+/// the package) rather than into the package's own `examples/` directory,
+/// so checking a README never mutates the source tree it's checking.
+fn compile_readme_example(
+    pkg_path: &Path,
+    crate_name: &str,
+    edition: &str,
+    index: usize,
+    example: &ReadmeExample,
+) -> Result<(), String> {
+    if example.ignore {
+        return Ok(());
+    }
+
+    let scratch = tempdir().map_err(|e| format!("{}", e))?;
+    let scratch_path = scratch.path();
+
+    fs::create_dir_all(scratch_path.join("src")).map_err(|e| format!("{}", e))?;
+    fs::write(
+        scratch_path.join("Cargo.toml"),
+        format!(
+            "[package]\n\
+             name = \"cargo_unleash_readme_doctest_{index}\"\n\
+             version = \"0.0.0\"\n\
+             edition = \"{edition}\"\n\
+             publish = false\n\
+             \n\
+             [dependencies]\n\
+             {crate_name} = {{ path = {pkg_path:?} }}\n",
+            index = index,
+            crate_name = crate_name,
+            edition = edition,
+            pkg_path = pkg_path,
+        ),
+    )
+    .map_err(|e| format!("{}", e))?;
+    fs::write(
+        scratch_path.join("src").join("main.rs"),
+        wrap_example(&example.code),
+    )
+    .map_err(|e| format!("{}", e))?;
+
+    run_cargo_against_example(&scratch_path.join("Cargo.toml"), example)
+}
+
+fn run_cargo_against_example(manifest_path: &Path, example: &ReadmeExample) -> Result<(), String> {
+    let action = if example.no_run || example.compile_fail {
+        "build"
+    } else {
+        "run"
+    };
+
+    let output = Command::new("cargo")
+        .arg(action)
+        .arg("--manifest-path")
+        .arg(manifest_path)
+        .output()
+        .map_err(|e| format!("line {}: failed to invoke cargo: {}", example.line, e))?;
+
+    if example.compile_fail {
+        return if output.status.success() {
+            Err(format!(
+                "line {}: expected compile_fail but the example compiled successfully",
+                example.line
+            ))
+        } else {
+            Ok(())
+        };
+    }
+
+    if example.should_panic && action == "run" {
+        return if output.status.success() {
+            Err(format!(
+                "line {}: expected should_panic but the example ran successfully",
+                example.line
+            ))
+        } else {
+            Ok(())
+        };
+    }
+
+    if !output.status.success() {
+        return Err(format!(
+            "line {}: {}\n{}",
+            example.line,
+            if action == "build" {
+                "failed to compile"
+            } else {
+                "failed to compile or run"
+            },
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+
+    Ok(())
+}
+
+/// Verify every Rust code block in the generated README that isn't marked
+/// `ignore` actually compiles (and runs, unless marked
+/// `no_run`/`compile_fail`), giving the
+/// same guarantee `cargo test --doc` gives for rustdoc examples but for the
+/// published README. Failures are reported per-block with the line number
+/// they start on in the README.
+pub fn check_readme_examples_compile(
+    pkg_path: &Path,
+    crate_name: &str,
+    edition: &str,
+    readme: &str,
+) -> Result<(), String> {
+    let examples = extract_readme_examples(readme);
+    let failures: Vec<String> = examples
+        .iter()
+        .enumerate()
+        .filter_map(|(i, example)| {
+            compile_readme_example(pkg_path, crate_name, edition, i, example).err()
+        })
+        .collect();
+
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(failures.join("\n"))
+    }
+}
+
+fn set_readme_field(pkg: Package, readme_path: &Path) -> Result<(), Box<dyn Error>> {
+    let pkg_path = pkg.manifest_path().parent().expect("Folder exists");
+    let relative = readme_path
+        .strip_prefix(pkg_path)
+        .unwrap_or(readme_path)
+        .to_str()
+        .expect("Valid path")
+        .to_owned();
     commands::set_field(
         vec![pkg].iter(),
         "package".to_owned(),
         "readme".to_owned(),
-        Value::from("README.md"),
+        Value::from(relative),
     )
 }
 
+/// The declared `readme` field of a package's manifest, before resolving it
+/// to a concrete path. `cargo::core::Manifest` only exposes the already
+/// normalized value, which can't tell `readme = false` apart from the field
+/// being unset, so this is read straight out of the manifest TOML.
+enum ReadmeField {
+    /// No `readme` key: defaults to `README.md`.
+    Unset,
+    /// `readme = false`: the package has no README.
+    Disabled,
+    /// `readme = "path/to/FILE.md"`.
+    Path(String),
+    /// `readme.workspace = true`: inherit the workspace's default.
+    WorkspaceInherited,
+}
+
+fn readme_field_from_value(value: Option<&Value>) -> ReadmeField {
+    let value = match value {
+        Some(value) => value,
+        None => return ReadmeField::Unset,
+    };
+
+    if let Some(b) = value.as_bool() {
+        return if b {
+            ReadmeField::Unset
+        } else {
+            ReadmeField::Disabled
+        };
+    }
+    if let Some(s) = value.as_str() {
+        return ReadmeField::Path(s.to_owned());
+    }
+    if let Some(table) = value.as_inline_table() {
+        if table.get("workspace").and_then(Value::as_bool) == Some(true) {
+            return ReadmeField::WorkspaceInherited;
+        }
+    }
+
+    ReadmeField::Unset
+}
+
+fn read_readme_field(manifest_path: &Path) -> Result<ReadmeField, String> {
+    let content = fs::read_to_string(manifest_path).map_err(|e| format!("{}", e))?;
+    let doc = content
+        .parse::<toml_edit::Document>()
+        .map_err(|e| format!("{}", e))?;
+
+    Ok(readme_field_from_value(
+        doc.get("package")
+            .and_then(|p| p.get("readme"))
+            .and_then(|i| i.as_value()),
+    ))
+}
+
+/// The `[workspace.package].readme` default a member inherits from via
+/// `readme.workspace = true`. A virtual (or root) manifest's `readme` lives
+/// under `[workspace.package]`, not `[package]`, so this can't share
+/// `read_readme_field`'s lookup path.
+fn read_workspace_readme_field(ws_manifest_path: &Path) -> Result<ReadmeField, String> {
+    let content = fs::read_to_string(ws_manifest_path).map_err(|e| format!("{}", e))?;
+    let doc = content
+        .parse::<toml_edit::Document>()
+        .map_err(|e| format!("{}", e))?;
+
+    Ok(readme_field_from_value(
+        doc.get("workspace")
+            .and_then(|w| w.get("package"))
+            .and_then(|p| p.get("readme"))
+            .and_then(|i| i.as_value()),
+    ))
+}
+
+/// Resolve the real path to a package's README, honoring the `readme` field:
+/// a custom path, `false` (no README), or `readme.workspace = true`
+/// inheritance from the workspace root's own `readme` default. Falls back to
+/// `README.md` when the field is unset.
+///
+/// Returns `None` when the package has explicitly opted out via
+/// `readme = false`.
+fn resolve_readme_path<'a>(
+    ws: &Workspace<'a>,
+    pkg_path: &Path,
+    manifest_path: &Path,
+) -> Result<Option<PathBuf>, String> {
+    let (base, name) = match read_readme_field(manifest_path)? {
+        ReadmeField::Disabled => return Ok(None),
+        ReadmeField::Path(name) => (pkg_path, name),
+        ReadmeField::Unset => (pkg_path, "README.md".to_owned()),
+        ReadmeField::WorkspaceInherited => {
+            let ws_manifest_path = ws.root().join("Cargo.toml");
+            let name = match read_workspace_readme_field(&ws_manifest_path)? {
+                ReadmeField::Path(name) => name,
+                _ => "README.md".to_owned(),
+            };
+            // Cargo resolves a workspace-inherited `readme` relative to the
+            // workspace root, not the member package's own directory.
+            (ws.root(), name)
+        }
+    };
+
+    Ok(Some(base.join(name)))
+}
+
 /// Find the default entrypoint to read the doc comments from
 ///
 /// Try to read entrypoint in the following order:
+/// - the manifest's `[lib]` target
+/// - the manifest's first `[[bin]]` target
 /// - src/lib.rs
 /// - src/main.rs
-fn find_entrypoint(current_dir: &Path) -> Result<File, String> {
-    let entrypoint = find_entrypoint_internal(current_dir)?;
-    File::open(current_dir.join(entrypoint)).map_err(|e| format!("{}", e))
+fn find_entrypoint(current_dir: &Path, manifest: &Manifest) -> Result<File, String> {
+    let entrypoint = find_entrypoint_internal(current_dir, manifest)?;
+    File::open(&entrypoint).map_err(|e| format!("{}", e))
 }
 #[derive(Debug)]
 struct ManifestLib {
@@ -186,19 +699,62 @@ struct ManifestLib {
     pub doc: bool,
 }
 
+/// Find the manifest's first target matching `is_match`, with its source
+/// path and whether it is documented (`doc = false` clears that flag).
+fn manifest_target(
+    manifest: &Manifest,
+    is_match: impl Fn(&cargo::core::Target) -> bool,
+) -> Option<ManifestLib> {
+    manifest
+        .targets()
+        .iter()
+        .find(|t| is_match(t))
+        .map(|t| ManifestLib {
+            path: t.src_path().path().to_path_buf(),
+            doc: t.documented(),
+        })
+}
+
 /// Find the default entrypoint to read the doc comments from
 ///
 /// Try to read entrypoint in the following order:
+/// - the manifest's `[lib]` target
+/// - the manifest's first `[[bin]]` target
 /// - src/lib.rs
 /// - src/main.rs
-fn find_entrypoint_internal(current_dir: &Path) -> Result<PathBuf, String> {
-    // try lib.rs
+///
+/// Targets with `doc = false` are skipped, since they opted out of
+/// documentation entirely. When the manifest has a lib or bin target but
+/// none of them is documented, no entrypoint is found; the conventional
+/// `src/lib.rs`/`src/main.rs` paths are not tried in that case.
+fn find_entrypoint_internal(current_dir: &Path, manifest: &Manifest) -> Result<PathBuf, String> {
+    let lib = manifest_target(manifest, |t| t.is_lib());
+    if let Some(lib) = lib.as_ref().filter(|lib| lib.doc) {
+        return Ok(lib.path.clone());
+    }
+
+    if let Some(bin) = manifest_target(manifest, |t| t.is_bin()) {
+        return if bin.doc {
+            Ok(bin.path)
+        } else {
+            // The bin target opted out of documentation; don't fall through
+            // to a path-based guess that would silently re-pick it.
+            Err("No entrypoint found".to_owned())
+        };
+    }
+
+    if lib.is_some() {
+        // Same as above, but for a lib target that opted out.
+        return Err("No entrypoint found".to_owned());
+    }
+
+    // fall back to the conventional locations, for manifests whose targets
+    // couldn't be resolved above
     let lib_rs = current_dir.join("src/lib.rs");
     if lib_rs.exists() {
         return Ok(lib_rs);
     }
 
-    // try main.rs
     let main_rs = current_dir.join("src/main.rs");
     if main_rs.exists() {
         return Ok(main_rs);
@@ -229,24 +785,245 @@ fn find_readme_template<'a>(
     })
 }
 
+/// Splice the freshly generated body into the existing README between the
+/// `SYNC_START_MARKER`/`SYNC_END_MARKER` sentinel comments.
+///
+/// - If both markers are present, only the text between them is replaced,
+///   leaving everything before the start marker and after the end marker
+///   untouched.
+/// - If neither marker is present, both markers and the generated body are
+///   appended to the end of the existing content.
+/// - If only one marker is found, or the end marker comes before the start
+///   marker, this is reported as an error rather than guessed at.
+fn sync_readme(existing: Option<&str>, generated: &str) -> Result<String, String> {
+    let existing = existing.unwrap_or("");
+    let start = existing.find(SYNC_START_MARKER);
+    let end = existing.find(SYNC_END_MARKER);
+
+    match (start, end) {
+        (Some(start_idx), Some(end_idx)) => {
+            let content_start = start_idx + SYNC_START_MARKER.len();
+            if end_idx < content_start {
+                return Err(format!(
+                    "Found {} before {} in README",
+                    SYNC_END_MARKER, SYNC_START_MARKER
+                ));
+            }
+            Ok(format!(
+                "{}\n{}\n{}",
+                &existing[..content_start],
+                generated.trim_end(),
+                &existing[end_idx..]
+            ))
+        }
+        (None, None) => {
+            let mut synced = existing.to_owned();
+            if !synced.is_empty() && !synced.ends_with('\n') {
+                synced.push('\n');
+            }
+            synced.push_str(SYNC_START_MARKER);
+            synced.push('\n');
+            synced.push_str(generated.trim_end());
+            synced.push('\n');
+            synced.push_str(SYNC_END_MARKER);
+            synced.push('\n');
+            Ok(synced)
+        }
+        (Some(_), None) => Err(format!(
+            "Found {} without matching {} in README",
+            SYNC_START_MARKER, SYNC_END_MARKER
+        )),
+        (None, Some(_)) => Err(format!(
+            "Found {} without matching {} in README",
+            SYNC_END_MARKER, SYNC_START_MARKER
+        )),
+    }
+}
+
+/// Rewrite a link destination found in the README into its docs.rs
+/// equivalent. Returns `None` when the destination doesn't need rewriting
+/// (already absolute, or not one of the relative forms rustdoc emits), in
+/// which case the original link is left untouched.
+fn rewrite_doc_link_dest(
+    dest: &str,
+    pkg_name: &str,
+    crate_underscored: &str,
+    doc_uri: &str,
+) -> Option<String> {
+    if dest.starts_with("http://") || dest.starts_with("https://") {
+        None
+    } else if let Some(rest) = dest.strip_prefix("../") {
+        Some(format!(
+            "{}{}",
+            doc_uri,
+            rest.replace('_', "-").replace("/index.html", "")
+        ))
+    } else if let Some(rest) = dest.strip_prefix("./") {
+        Some(format!(
+            "{}{}/latest/{}/{}",
+            doc_uri, pkg_name, crate_underscored, rest
+        ))
+    } else {
+        None
+    }
+}
+
+/// Rustdoc intra-doc shortcut links look like `` [`Type`] `` or
+/// `` [`mod::Type`] `` — a code span as the whole link text, with no
+/// explicit destination.
+fn intra_doc_item(reference: &str) -> Option<&str> {
+    reference
+        .strip_prefix('`')
+        .and_then(|s| s.strip_suffix('`'))
+        .filter(|s| !s.is_empty())
+}
+
+/// Best-effort mapping from a rustdoc item path (`mod::Type`) to a docs.rs
+/// URL. docs.rs file names actually depend on the item's kind (`struct.`,
+/// `fn.`, ...), which isn't available from the README text alone, so this
+/// only rewrites the module path and leaves the rest to docs.rs's own
+/// redirects.
+fn intra_doc_url(doc_uri: &str, pkg_name: &str, crate_underscored: &str, item: &str) -> String {
+    format!(
+        "{}{}/latest/{}/{}",
+        doc_uri,
+        pkg_name,
+        crate_underscored,
+        item.replace("::", "/")
+    )
+}
+
+/// Rewrite relative rustdoc links (`./...`, `../...`) and rustdoc intra-doc
+/// links (`` [`Type`] ``) in a generated README into absolute docs.rs URLs.
+///
+/// Unlike the previous regex-based pass, this walks a real markdown parser
+/// so only genuine link and link-definition events are touched: link text
+/// and destinations inside code spans or fenced code blocks are never
+/// matched, and reference-style links (`[text][id]`) are resolved through
+/// their `[id]: url` definition the same way a markdown renderer would.
 fn fix_doc_links(pkg_name: &str, readme: &str, doc_uri: Option<&str>) -> String {
-    RELATIVE_LINKS_REGEX
-        .replace_all(&readme, |caps: &Captures| match caps.name("url") {
-            Some(url) if url.as_str().starts_with("../") => format!(
-                "[{}]({}{})",
-                &caps.name("text").unwrap().as_str(),
-                doc_uri.unwrap_or(DEFAULT_DOC_URI),
-                &url.as_str().replace('_', "-").replace("/index.html", "")[3..]
-            ),
-            Some(url) if url.as_str().starts_with("./") => format!(
-                "[{}]({}{}/latest/{}/{})",
-                &caps.name("text").unwrap().as_str(),
-                doc_uri.unwrap_or(DEFAULT_DOC_URI),
-                pkg_name,
-                pkg_name.replace('-', "_"),
-                &url.as_str()[2..]
-            ),
-            _ => caps[0].to_string(),
-        })
-        .into()
+    let doc_uri = doc_uri.unwrap_or(DEFAULT_DOC_URI);
+    let crate_underscored = pkg_name.replace('-', "_");
+
+    let mut intra_doc_callback = |broken_link: pulldown_cmark::BrokenLink| {
+        let item = intra_doc_item(broken_link.reference.as_ref())?;
+        Some((
+            intra_doc_url(doc_uri, pkg_name, &crate_underscored, item).into(),
+            String::new().into(),
+        ))
+    };
+
+    let parser = Parser::new_with_broken_link_callback(
+        readme,
+        pulldown_cmark::Options::empty(),
+        Some(&mut intra_doc_callback),
+    );
+
+    let mut edits: Vec<(std::ops::Range<usize>, String)> = Vec::new();
+    let mut open_links: Vec<(std::ops::Range<usize>, LinkType, String, String)> = Vec::new();
+
+    for (event, range) in parser.into_offset_iter() {
+        match event {
+            Event::Start(Tag::Link(link_type, dest, _title)) => {
+                open_links.push((range, link_type, dest.into_string(), String::new()));
+            }
+            Event::Text(text) => {
+                if let Some((_, _, _, text_acc)) = open_links.last_mut() {
+                    text_acc.push_str(&text);
+                }
+            }
+            Event::Code(text) => {
+                if let Some((_, _, _, text_acc)) = open_links.last_mut() {
+                    // keep the code span markers so e.g. `` [`Foo`](url) ``
+                    // still renders as code, not as plain text.
+                    text_acc.push('`');
+                    text_acc.push_str(&text);
+                    text_acc.push('`');
+                }
+            }
+            Event::End(Tag::Link(..)) => {
+                if let Some((range, link_type, dest, text)) = open_links.pop() {
+                    // `*Unknown` link types are exactly the ones resolved
+                    // through the broken-link callback above (e.g. an
+                    // intra-doc shortcut link): the source never had a
+                    // destination at all, so the link must always be
+                    // rewritten in, using the dest our callback already
+                    // computed.
+                    let new_dest = if matches!(
+                        link_type,
+                        LinkType::ShortcutUnknown
+                            | LinkType::CollapsedUnknown
+                            | LinkType::ReferenceUnknown
+                    ) {
+                        Some(dest)
+                    } else {
+                        rewrite_doc_link_dest(&dest, pkg_name, &crate_underscored, doc_uri)
+                    };
+                    if let Some(new_dest) = new_dest {
+                        edits.push((range, format!("[{}]({})", text, new_dest)));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut result = String::with_capacity(readme.len());
+    let mut cursor = 0;
+    for (range, replacement) in edits {
+        result.push_str(&readme[cursor..range.start]);
+        result.push_str(&replacement);
+        cursor = range.end;
+    }
+    result.push_str(&readme[cursor..]);
+    result
+}
+
+#[cfg(test)]
+mod fix_doc_links_tests {
+    use super::fix_doc_links;
+
+    #[test]
+    fn intra_doc_shortcut_link_becomes_docs_rs_url() {
+        let readme = "See [`Foo`] for details.";
+        let fixed = fix_doc_links("my-crate", readme, None);
+        assert_eq!(
+            fixed,
+            "See [`Foo`](https://docs.rs/my-crate/latest/my_crate/Foo) for details."
+        );
+    }
+
+    #[test]
+    fn intra_doc_path_link_becomes_docs_rs_url() {
+        let readme = "See [`some::Item`] for details.";
+        let fixed = fix_doc_links("my-crate", readme, None);
+        assert_eq!(
+            fixed,
+            "See [`some::Item`](https://docs.rs/my-crate/latest/my_crate/some/Item) for details."
+        );
+    }
+
+    #[test]
+    fn relative_rustdoc_link_is_rewritten() {
+        let readme = "[Foo](./struct.Foo.html)";
+        let fixed = fix_doc_links("my-crate", readme, None);
+        assert_eq!(
+            fixed,
+            "[Foo](https://docs.rs/my-crate/latest/my_crate/struct.Foo.html)"
+        );
+    }
+
+    #[test]
+    fn absolute_link_is_left_untouched() {
+        let readme = "[Foo](https://example.com/foo)";
+        let fixed = fix_doc_links("my-crate", readme, None);
+        assert_eq!(fixed, readme);
+    }
+
+    #[test]
+    fn code_span_outside_a_link_is_left_untouched() {
+        let readme = "Call `foo::bar()` to start.";
+        let fixed = fix_doc_links("my-crate", readme, None);
+        assert_eq!(fixed, readme);
+    }
 }